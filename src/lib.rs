@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     env, error, fmt,
     fs::File,
     io::{self, BufRead, BufReader},
@@ -78,27 +79,268 @@ where
     init_inner(reader, mode)
 }
 
+/// Like [`init`], but a missing `.env` file is treated as a successful no-op
+/// instead of an error. Other IO errors are still propagated.
+pub fn init_optional<T>(mode: T) -> Result<(), FaktorError>
+where
+    T: SetEnvVar,
+{
+    from_file_optional(".env", mode)
+}
+
+/// Like [`from_file`], but a missing file is treated as a successful no-op
+/// instead of an error. Other IO errors are still propagated.
+pub fn from_file_optional<T>(filename: &str, mode: T) -> Result<(), FaktorError>
+where
+    T: SetEnvVar,
+{
+    match File::open(filename) {
+        Ok(file) => init_inner(BufReader::new(file), mode),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
 fn init_inner<R, T>(reader: R, mode: T) -> Result<(), FaktorError>
 where
     T: SetEnvVar,
     R: BufRead,
 {
-    let lines = reader.lines();
+    for (key, value) in parse_pairs(reader)? {
+        mode.set(&key, value.as_deref())?;
+    }
+    Ok(())
+}
 
-    for line in lines {
+/// Parses `reader` and returns the key/value pairs without touching the
+/// process environment, leaving it to the caller to apply them via
+/// [`Override`]/[`Skip`] or inspect them directly. Keys that appear without a
+/// value map to an empty string.
+pub fn parse<R>(reader: R) -> Result<Vec<(String, String)>, FaktorError>
+where
+    R: BufRead,
+{
+    Ok(parse_pairs(reader)?
+        .into_iter()
+        .map(|(key, value)| (key, value.unwrap_or_default()))
+        .collect())
+}
+
+/// Convenience wrapper around [`parse`] that reads from a file.
+pub fn parse_file(filename: &str) -> Result<Vec<(String, String)>, FaktorError> {
+    let file = File::open(filename)?;
+    parse(BufReader::new(file))
+}
+
+/// Shared parsing core used by both [`parse`] and `init_inner`. Skips comments
+/// and blank lines, splits on the first `=`, normalizes quoting and escapes,
+/// and expands variable references against earlier keys in the same file (and
+/// the process environment). A value is `None` only when the line has no `=`.
+fn parse_pairs<R>(reader: R) -> Result<Vec<(String, Option<String>)>, FaktorError>
+where
+    R: BufRead,
+{
+    let mut local: HashMap<String, String> = HashMap::new();
+    let mut pairs = Vec::new();
+    let mut lines = reader.lines();
+
+    while let Some(line) = lines.next() {
         let line = line?;
         let line = line.trim();
         if line.starts_with('#') {
             continue;
         }
+        let line = line.strip_prefix("export ").unwrap_or(line);
         let (key, value) = split_once(line);
         let key = key.trim();
         if key.is_empty() {
             continue;
         }
-        mode.set(key, value)?;
+        let value = match value {
+            Some(value) => {
+                let NormalizedValue { text, expandable } = normalize_value(value, &mut lines)?;
+                Some(if expandable {
+                    expand(&text, &local)
+                } else {
+                    text
+                })
+            }
+            None => None,
+        };
+        if let Some(value) = &value {
+            local.insert(key.to_string(), value.clone());
+        }
+        pairs.push((key.to_string(), value));
     }
-    Ok(())
+    Ok(pairs)
+}
+
+/// A value after quote stripping and escape processing, along with whether it
+/// should still be subject to variable expansion (single-quoted values are
+/// taken literally).
+struct NormalizedValue {
+    text: String,
+    expandable: bool,
+}
+
+/// Normalizes a raw value extracted by [`split_once`]. Values wrapped in a
+/// matching pair of quotes have the quotes stripped; double-quoted values
+/// honour `\n`, `\t`, `\r`, `\\` and `\"` escapes and may span several input
+/// lines (pulled from `lines`) until the closing quote, whereas single-quoted
+/// values are literal. Unquoted values have trailing inline comments removed.
+fn normalize_value<I>(raw: &str, lines: &mut I) -> Result<NormalizedValue, FaktorError>
+where
+    I: Iterator<Item = io::Result<String>>,
+{
+    let raw = raw.trim();
+    match raw.chars().next() {
+        Some('"') => read_quoted(raw, '"', true, lines),
+        Some('\'') => read_quoted(raw, '\'', false, lines),
+        _ => Ok(NormalizedValue {
+            text: strip_inline_comment(raw).trim_end().to_string(),
+            expandable: true,
+        }),
+    }
+}
+
+/// Scans a quoted value, pulling additional lines from `lines` while the
+/// closing quote has not been seen. The opening quote is the first character
+/// of `first`. An unterminated value at end of input yields its accumulated
+/// content on a best-effort basis.
+fn read_quoted<I>(
+    first: &str,
+    quote: char,
+    process_escapes: bool,
+    lines: &mut I,
+) -> Result<NormalizedValue, FaktorError>
+where
+    I: Iterator<Item = io::Result<String>>,
+{
+    let mut buf = first.to_string();
+    loop {
+        let (text, closed) = scan_quoted(&buf, quote, process_escapes);
+        if closed {
+            return Ok(NormalizedValue {
+                text,
+                expandable: process_escapes,
+            });
+        }
+        match lines.next() {
+            Some(line) => {
+                buf.push('\n');
+                buf.push_str(&line?);
+            }
+            None => {
+                return Ok(NormalizedValue {
+                    text,
+                    expandable: process_escapes,
+                });
+            }
+        }
+    }
+}
+
+/// Reads the body of a quoted string whose opening quote is the first
+/// character of `buf`, returning the unquoted content and whether the closing
+/// quote was found.
+fn scan_quoted(buf: &str, quote: char, process_escapes: bool) -> (String, bool) {
+    let mut chars = buf.chars();
+    chars.next();
+    let mut out = String::new();
+
+    while let Some(c) = chars.next() {
+        if process_escapes && c == '\\' {
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('\\') => out.push('\\'),
+                Some('"') => out.push('"'),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                }
+                None => out.push('\\'),
+            }
+        } else if c == quote {
+            return (out, true);
+        } else {
+            out.push(c);
+        }
+    }
+    (out, false)
+}
+
+/// Removes a trailing inline comment (a `#` preceded by whitespace, or at the
+/// start of the value) from an unquoted value.
+fn strip_inline_comment(value: &str) -> &str {
+    let bytes = value.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'#' && (i == 0 || bytes[i - 1].is_ascii_whitespace()) {
+            return &value[..i];
+        }
+    }
+    value
+}
+
+/// Expands `${VAR}` and bare `$VAR` references in a value, looking each name
+/// up in the file-local map first and then the process environment, falling
+/// back to an empty string when unset. A literal dollar sign is written as
+/// `$$` or `\$`.
+fn expand(value: &str, local: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                out.push('$');
+            }
+            '$' if chars.peek() == Some(&'$') => {
+                chars.next();
+                out.push('$');
+            }
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '}' {
+                        chars.next();
+                        break;
+                    }
+                    name.push(c);
+                    chars.next();
+                }
+                out.push_str(&lookup(&name, local));
+            }
+            '$' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        name.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if name.is_empty() {
+                    out.push('$');
+                } else {
+                    out.push_str(&lookup(&name, local));
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn lookup(name: &str, local: &HashMap<String, String>) -> String {
+    local
+        .get(name)
+        .cloned()
+        .unwrap_or_else(|| env::var(name).unwrap_or_default())
 }
 
 fn split_once(in_string: &str) -> (&str, Option<&str>) {
@@ -194,6 +436,169 @@ mod tests {
         assert_eq!("OLDTESTVALUE", env::var("TESTKEY").unwrap());
     }
 
+    #[test]
+    fn test_expand_braced_and_bare() {
+        let mut local = HashMap::new();
+        local.insert("HOST".to_string(), "localhost".to_string());
+        local.insert("PORT".to_string(), "8080".to_string());
+        assert_eq!("localhost:8080/api", expand("${HOST}:${PORT}/api", &local));
+        assert_eq!("localhost:8080", expand("$HOST:$PORT", &local));
+    }
+
+    #[test]
+    fn test_expand_adjacent_references() {
+        let mut local = HashMap::new();
+        local.insert("A".to_string(), "foo".to_string());
+        local.insert("B".to_string(), "bar".to_string());
+        assert_eq!("foobar", expand("${A}${B}", &local));
+    }
+
+    #[test]
+    fn test_expand_undefined_is_empty() {
+        let local = HashMap::new();
+        env::remove_var("FAKTOR_MISSING");
+        assert_eq!("x/y", expand("x$FAKTOR_MISSING/${FAKTOR_MISSING}y", &local));
+    }
+
+    #[test]
+    fn test_expand_escaped_dollar() {
+        let mut local = HashMap::new();
+        local.insert("VAR".to_string(), "value".to_string());
+        assert_eq!("${VAR}", expand("$${VAR}", &local));
+        assert_eq!("$VAR", expand("\\$VAR", &local));
+        assert_eq!("price: $5", expand("price: $$5", &local));
+    }
+
+    #[test]
+    fn test_expand_falls_back_to_environment() {
+        let mut local = HashMap::new();
+        local.insert("LOCAL".to_string(), "file".to_string());
+        env::set_var("FAKTOR_ENV_ONLY", "proc");
+        assert_eq!("file-proc", expand("${LOCAL}-${FAKTOR_ENV_ONLY}", &local));
+    }
+
+    #[test]
+    fn test_init_expands_earlier_keys() {
+        env::remove_var("FAKTOR_HOST");
+        env::remove_var("FAKTOR_URL");
+        let input = "FAKTOR_HOST=localhost\nFAKTOR_URL=http://${FAKTOR_HOST}/api".as_bytes();
+        let res = init_inner(input, crate::Override);
+        assert_eq!(true, res.is_ok());
+        assert_eq!("http://localhost/api", env::var("FAKTOR_URL").unwrap());
+    }
+
+    #[test]
+    fn test_init_forward_reference_is_empty() {
+        env::remove_var("FAKTOR_FWD_A");
+        env::remove_var("FAKTOR_FWD_B");
+        let input = "FAKTOR_FWD_A=${FAKTOR_FWD_B}x\nFAKTOR_FWD_B=late".as_bytes();
+        let res = init_inner(input, crate::Override);
+        assert_eq!(true, res.is_ok());
+        assert_eq!("x", env::var("FAKTOR_FWD_A").unwrap());
+    }
+
+    #[test]
+    fn test_init_strips_double_quotes() {
+        env::remove_var("FAKTOR_DQ");
+        let input = r#"FAKTOR_DQ="value""#.as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!("value", env::var("FAKTOR_DQ").unwrap());
+    }
+
+    #[test]
+    fn test_init_single_quotes_are_literal() {
+        env::remove_var("FAKTOR_SQ");
+        let input = r#"FAKTOR_SQ='no\nescape ${NOPE}'"#.as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!(r#"no\nescape ${NOPE}"#, env::var("FAKTOR_SQ").unwrap());
+    }
+
+    #[test]
+    fn test_init_double_quote_escapes() {
+        env::remove_var("FAKTOR_ESC");
+        let input = r#"FAKTOR_ESC="a\tb\nc\"d""#.as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!("a\tb\nc\"d", env::var("FAKTOR_ESC").unwrap());
+    }
+
+    #[test]
+    fn test_init_strips_inline_comment() {
+        env::remove_var("FAKTOR_CMT");
+        let input = "FAKTOR_CMT=value # trailing".as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!("value", env::var("FAKTOR_CMT").unwrap());
+    }
+
+    #[test]
+    fn test_init_multiline_double_quoted() {
+        env::remove_var("FAKTOR_ML");
+        let input = "FAKTOR_ML=\"line one\nline two\"\nFAKTOR_AFTER=done".as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!("line one\nline two", env::var("FAKTOR_ML").unwrap());
+        assert_eq!("done", env::var("FAKTOR_AFTER").unwrap());
+    }
+
+    #[test]
+    fn test_strip_inline_comment_keeps_hash_in_word() {
+        assert_eq!("a#b", strip_inline_comment("a#b"));
+        assert_eq!("a ", strip_inline_comment("a # b"));
+    }
+
+    #[test]
+    fn test_parse_returns_pairs_without_mutating_env() {
+        env::remove_var("FAKTOR_PARSE_ONLY");
+        let input = "FAKTOR_PARSE_ONLY=value\n# comment\nOTHER=42".as_bytes();
+        let pairs = parse(input).unwrap();
+        assert_eq!(
+            vec![
+                ("FAKTOR_PARSE_ONLY".to_string(), "value".to_string()),
+                ("OTHER".to_string(), "42".to_string()),
+            ],
+            pairs
+        );
+        assert_eq!(Err(VarError::NotPresent), env::var("FAKTOR_PARSE_ONLY"));
+    }
+
+    #[test]
+    fn test_parse_expands_earlier_keys() {
+        env::remove_var("FAKTOR_P_HOST");
+        let input = "FAKTOR_P_HOST=localhost\nURL=http://${FAKTOR_P_HOST}".as_bytes();
+        let pairs = parse(input).unwrap();
+        assert_eq!("http://localhost", pairs[1].1);
+    }
+
+    #[test]
+    fn test_parse_missing_value_is_empty_string() {
+        let pairs = parse("JUSTKEY".as_bytes()).unwrap();
+        assert_eq!(vec![("JUSTKEY".to_string(), String::new())], pairs);
+    }
+
+    #[test]
+    fn test_init_strips_export_prefix() {
+        env::remove_var("FAKTOR_EXPORTED");
+        let input = "export FAKTOR_EXPORTED=value".as_bytes();
+        assert!(init_inner(input, crate::Override).is_ok());
+        assert_eq!("value", env::var("FAKTOR_EXPORTED").unwrap());
+    }
+
+    #[test]
+    fn test_parse_keeps_export_word_as_part_of_key() {
+        let pairs = parse("exported=1".as_bytes()).unwrap();
+        assert_eq!(vec![("exported".to_string(), "1".to_string())], pairs);
+    }
+
+    #[test]
+    fn test_from_file_optional_missing_is_ok() {
+        let res = from_file_optional("this_file_does_not_exist.env", crate::Override);
+        assert_eq!(true, res.is_ok());
+    }
+
+    #[test]
+    fn test_from_file_missing_is_err() {
+        let res = from_file("this_file_does_not_exist.env", crate::Override);
+        assert_eq!(true, res.is_err());
+    }
+
     #[test]
     fn test_error_impl() {
         let err = FaktorError::Io(io::Error::new(io::ErrorKind::AddrInUse, "error"));